@@ -0,0 +1,35 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyByteArray;
+
+/// Borrow a mutable, zero-copy view over `output` to use as a compression/decompression target.
+///
+/// Accepts a writable `bytearray`, or any other object implementing the buffer protocol with
+/// a writable, contiguous buffer (e.g. a non-readonly `memoryview`).
+///
+/// The returned slice aliases Python-owned memory. This is only sound because `_native` does
+/// not declare `gil_used = false`: the GIL is held for the entire call, so no other thread can
+/// resize or otherwise invalidate `output`'s backing buffer while it is borrowed here.
+pub fn writable_bytes<'py>(output: &Bound<'py, PyAny>) -> PyResult<&'py mut [u8]> {
+    if let Ok(bytearray) = output.downcast::<PyByteArray>() {
+        // SAFETY: the GIL is held for the duration of the call (see the note on this function),
+        // so `output` cannot be concurrently mutated out from under this slice, matching
+        // `PyByteArray::as_bytes_mut`'s safety contract.
+        return Ok(unsafe { bytearray.as_bytes_mut() });
+    }
+
+    let buffer = pyo3::buffer::PyBuffer::<u8>::get(output)?;
+    if buffer.readonly() {
+        return Err(PyErr::new::<PyValueError, _>(
+            "output buffer is read-only".to_string(),
+        ));
+    }
+
+    let cells = buffer.as_mut_slice(output.py()).ok_or_else(|| {
+        PyErr::new::<PyValueError, _>("output buffer is not contiguous".to_string())
+    })?;
+
+    // SAFETY: `Cell<u8>` has the same layout as `u8`, and we just checked that the buffer
+    // is writable and contiguous.
+    Ok(unsafe { std::slice::from_raw_parts_mut(cells.as_ptr() as *mut u8, cells.len()) })
+}
@@ -2,10 +2,12 @@ use pyo3::prelude::*;
 
 mod lz4;
 mod lzo;
+mod zstd;
 
 pub fn create_submodule(m: &Bound<'_, PyModule>) -> PyResult<()> {
     let submodule = PyModule::new_bound(m.py(), "compression")?;
     lz4::create_submodule(&submodule)?;
     lzo::create_submodule(&submodule)?;
+    zstd::create_submodule(&submodule)?;
     m.add_submodule(&submodule)
 }
@@ -0,0 +1,248 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes};
+
+use crate::buffer::writable_bytes;
+use crate::source::read_source;
+
+const MAX_DISCOVER_OUTPUT_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Resolve a `mode`/`acceleration`/`level` triple to a `lz4` `CompressionMode`.
+///
+/// `lz4_flex` only implements a single fast strategy, so tuning `compress`/`compress_into`
+/// is delegated to the `lz4` crate's bindings to liblz4, which genuinely honors
+/// `acceleration` (via `LZ4_compress_fast`) and `level` (via `LZ4_compress_HC`).
+fn resolve_mode(mode: &str, acceleration: i32, level: i32) -> PyResult<lz4::block::CompressionMode> {
+    match mode {
+        "default" => {
+            if acceleration < 1 {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "acceleration must be at least 1".to_string(),
+                ));
+            }
+            Ok(lz4::block::CompressionMode::FAST(acceleration))
+        }
+        "high_compression" => {
+            if !(1..=12).contains(&level) {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "level must be between 1 and 12".to_string(),
+                ));
+            }
+            Ok(lz4::block::CompressionMode::HIGHCOMPRESSION(level))
+        }
+        other => Err(PyErr::new::<PyValueError, _>(format!(
+            "invalid mode: {other}"
+        ))),
+    }
+}
+
+fn compress_with_mode(src: &[u8], mode: &str, acceleration: i32, level: i32) -> PyResult<Vec<u8>> {
+    let mode = resolve_mode(mode, acceleration, level)?;
+    lz4::block::compress(src, Some(mode), false)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+}
+
+fn decompress_to_unknown_size(src: &[u8]) -> Result<Vec<u8>, PyErr> {
+    let mut output_size = lz4_flex::block::get_maximum_output_size(src.len());
+    loop {
+        // If the output size is too large, we should not attempt to decompress further
+        if output_size > MAX_DISCOVER_OUTPUT_SIZE {
+            return Err(PyErr::new::<PyValueError, _>(
+                "output size is too large".to_string(),
+            ));
+        }
+
+        match lz4_flex::block::decompress(&src, output_size) {
+            Ok(result) => {
+                break Ok(result);
+            }
+            Err(lz4_flex::block::DecompressError::OutputTooSmall {
+                expected,
+                actual: _,
+            }) => {
+                output_size = expected;
+            }
+            Err(e) => {
+                return Err(PyErr::new::<PyValueError, _>(e.to_string()));
+            }
+        }
+    }
+}
+
+/// LZ4 decompress bytes up to a certain length. Assumes no header.
+///
+/// Args:
+///     src: Bytes to decompress, or a file-like object exposing ``read()``.
+///     uncompressed_size: The uncompressed data size. If not provided or ``-1``, will try to discover it.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The decompressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, uncompressed_size=-1, return_bytearray=false))]
+fn decompress(
+    py: Python<'_>,
+    src: &Bound<'_, PyAny>,
+    uncompressed_size: isize,
+    return_bytearray: bool,
+) -> PyResult<PyObject> {
+    let src = read_source(src)?;
+    let result = if uncompressed_size < 0 {
+        // If the uncompressed size is not provided, we need to discover it first
+        decompress_to_unknown_size(&src)?
+    } else {
+        lz4_flex::block::decompress(&src, uncompressed_size as usize)
+            .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?
+    };
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
+/// LZ4 decompress `src` directly into `output`. Assumes no header.
+///
+/// Unlike `decompress`, this writes straight into the caller-supplied buffer instead of
+/// allocating a new one, which is useful when the same buffer can be reused across calls.
+///
+/// Args:
+///     src: Bytes to decompress, or a file-like object exposing ``read()``.
+///     output: A writable ``bytearray`` or writable buffer to decompress into.
+///
+/// Returns:
+///     The number of bytes written to `output`.
+///
+#[pyfunction]
+fn decompress_into(src: &Bound<'_, PyAny>, output: &Bound<'_, PyAny>) -> PyResult<usize> {
+    let src = read_source(src)?;
+    let output = writable_bytes(output)?;
+    lz4_flex::block::decompress_into(&src, output)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+}
+
+/// LZ4 compress `src` directly into `output`. Assumes no header.
+///
+/// Unlike `compress`, this writes straight into the caller-supplied buffer instead of
+/// allocating a new one. Use `compress_block_bound` to size `output` beforehand.
+///
+/// Args:
+///     src: Bytes to compress.
+///     output: A writable ``bytearray`` or writable buffer to compress into.
+///     mode: Either ``"default"`` for the fast encoder or ``"high_compression"``.
+///     acceleration: Acceleration factor used by the ``"default"`` mode. Higher is faster but compresses worse.
+///     level: Compression level used by the ``"high_compression"`` mode, between ``1`` and ``12``.
+///
+/// Returns:
+///     The number of bytes written to `output`.
+///
+#[pyfunction]
+#[pyo3(signature = (src, output, mode="default", acceleration=1, level=9))]
+fn compress_into(
+    src: Vec<u8>,
+    output: &Bound<'_, PyAny>,
+    mode: &str,
+    acceleration: i32,
+    level: i32,
+) -> PyResult<usize> {
+    let mode = resolve_mode(mode, acceleration, level)?;
+
+    let output = writable_bytes(output)?;
+    lz4::block::compress_to_buffer(&src, Some(mode), false, output)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+}
+
+/// LZ4 compress bytes. Assumes no header.
+///
+/// Args:
+///     src: Bytes to compress.
+///     mode: Either ``"default"`` for the fast encoder or ``"high_compression"``.
+///     acceleration: Acceleration factor used by the ``"default"`` mode. Higher is faster but compresses worse.
+///     level: Compression level used by the ``"high_compression"`` mode, between ``1`` and ``12``.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The compressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, mode="default", acceleration=1, level=9, return_bytearray=false))]
+fn compress(
+    py: Python<'_>,
+    src: Vec<u8>,
+    mode: &str,
+    acceleration: i32,
+    level: i32,
+    return_bytearray: bool,
+) -> PyResult<PyObject> {
+    let result = compress_with_mode(&src, mode, acceleration, level)?;
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
+/// Compute the maximum possible size of `src` once LZ4-compressed.
+///
+/// Useful for pre-sizing an output buffer before calling `compress_into`.
+///
+/// Args:
+///     src_len: Length of the uncompressed data.
+///
+/// Returns:
+///     The maximum compressed size.
+///
+#[pyfunction]
+fn compress_block_bound(src_len: usize) -> usize {
+    lz4_flex::block::get_maximum_output_size(src_len)
+}
+
+pub fn create_submodule(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let submodule = PyModule::new(m.py(), "block")?;
+    submodule.add_function(wrap_pyfunction!(decompress, m)?)?;
+    submodule.add_function(wrap_pyfunction!(decompress_into, m)?)?;
+    submodule.add_function(wrap_pyfunction!(compress, m)?)?;
+    submodule.add_function(wrap_pyfunction!(compress_into, m)?)?;
+    submodule.add_function(wrap_pyfunction!(compress_block_bound, m)?)?;
+    m.add_submodule(&submodule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &[u8] = b"hello hello hello hello world, this is a test of lz4 block compression";
+
+    #[test]
+    fn default_mode_round_trips_across_backends() {
+        // `compress`/`compress_into` are backed by the `lz4` crate (liblz4), while
+        // `decompress`/`decompress_into` are backed by `lz4_flex`. Both implement the same
+        // LZ4 block format, so they must interoperate.
+        let compressed = compress_with_mode(SRC, "default", 1, 9).unwrap();
+        let decompressed = lz4_flex::block::decompress(&compressed, SRC.len()).unwrap();
+        assert_eq!(decompressed, SRC);
+    }
+
+    #[test]
+    fn high_compression_mode_round_trips_across_backends() {
+        let compressed = compress_with_mode(SRC, "high_compression", 1, 12).unwrap();
+        let decompressed = lz4_flex::block::decompress(&compressed, SRC.len()).unwrap();
+        assert_eq!(decompressed, SRC);
+    }
+
+    #[test]
+    fn invalid_mode_is_rejected() {
+        assert!(compress_with_mode(SRC, "bogus", 1, 9).is_err());
+    }
+
+    #[test]
+    fn out_of_range_tuning_parameters_are_rejected() {
+        assert!(compress_with_mode(SRC, "default", 0, 9).is_err());
+        assert!(compress_with_mode(SRC, "high_compression", 1, 13).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use std::io::{Read, Write};
+
+use lz4_flex::frame::{FrameDecoder, FrameEncoder};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes};
+
+use crate::source::open_reader;
+
+/// LZ4 frame decompress bytes.
+///
+/// Unlike `lz4.block.decompress`, `src` is expected to start with the LZ4 frame magic
+/// number (``0x184D2204``), followed by a frame descriptor and one or more (optionally
+/// checksummed) blocks, as produced by the standard `lz4` CLI and the `python-lz4` frame API.
+///
+/// Args:
+///     src: Bytes to decompress, or a file-like object exposing ``read()``, including the
+///         frame header. `FrameDecoder` consumes it incrementally, so large `.lz4` files do
+///         not need to be fully materialized in memory beforehand.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The decompressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, return_bytearray=false))]
+fn decompress(py: Python<'_>, src: &Bound<'_, PyAny>, return_bytearray: bool) -> PyResult<PyObject> {
+    let reader = open_reader(src)?;
+    let mut decoder = FrameDecoder::new(reader);
+    let mut result = Vec::new();
+    decoder
+        .read_to_end(&mut result)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
+/// LZ4 frame compress bytes.
+///
+/// Unlike `lz4.block.compress`, the output includes the frame magic number and frame
+/// descriptor, so it can be read by any standard LZ4 frame decoder (e.g. the `lz4` CLI or
+/// `python-lz4`). Block and content checksums are not written.
+///
+/// Args:
+///     src: Bytes to compress.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The compressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, return_bytearray=false))]
+fn compress(py: Python<'_>, src: Vec<u8>, return_bytearray: bool) -> PyResult<PyObject> {
+    let mut encoder = FrameEncoder::new(Vec::new());
+    encoder
+        .write_all(&src)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+    let result = encoder
+        .finish()
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
+pub fn create_submodule(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let submodule = PyModule::new(m.py(), "frame")?;
+    submodule.add_function(wrap_pyfunction!(decompress, m)?)?;
+    submodule.add_function(wrap_pyfunction!(compress, m)?)?;
+    m.add_submodule(&submodule)
+}
@@ -1,15 +1,28 @@
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyByteArray, PyBytes};
+
+use crate::buffer::writable_bytes;
+use crate::source::read_source;
+
+/// Strip the python-lzo compatible header from `src`, if present, returning the remaining
+/// compressed body and the uncompressed length it declares.
+///
+/// https://github.com/jd-boyd/python-lzo/blob/80ca60416c6657d373c5308a1eb511903a3ff9b1/lzomodule.c#L238-L269
+fn strip_header(src: Vec<u8>) -> PyResult<(Vec<u8>, usize)> {
+    if src.len() < 8 || src[0] < 0xf0 || src[0] > 0xf1 {
+        return Err(PyValueError::new_err("Invalid header value"));
+    }
+    let len = u32::from_le_bytes([src[1], src[2], src[3], src[4]]) as usize;
+    Ok((src[5..].to_vec(), len))
+}
 
 /// LZO decompress from bytes. Assumes no header.
 ///
 /// Arguments are largely compatible with python-lzo API.
 ///
-/// Unlike the Python implementation, this function does not support streaming decompression
-/// (i.e. reading from a file-like object).
 /// Args:
-///     src: Bytes to decompress.
+///     src: Bytes to decompress, or a file-like object exposing ``read()``.
 ///     header: Whether the metadata header is included in the input.
 ///     buflen: If ``header`` is ``False``, a buffer length in bytes must be given that will fit the output.
 ///
@@ -20,18 +33,14 @@ use pyo3::types::PyBytes;
 #[pyo3(signature = (src, header=true, buflen=-1))]
 fn decompress(
     py: Python<'_>,
-    src: Vec<u8>,
+    src: &Bound<'_, PyAny>,
     header: bool,
     buflen: isize,
 ) -> PyResult<Bound<'_, PyBytes>> {
+    let src = read_source(src)?;
     let (body, out_len) = if header {
-        // Compatibility with python-lzo, which can include a header
-        // https://github.com/jd-boyd/python-lzo/blob/80ca60416c6657d373c5308a1eb511903a3ff9b1/lzomodule.c#L238-L269
-        if src.len() < 8 || src[0] < 0xf0 || src[0] > 0xf1 {
-            return Err(PyValueError::new_err("Invalid header value"));
-        }
-        let len = u32::from_le_bytes([src[1], src[2], src[3], src[4]]) as usize;
-        (src[5..].to_vec(), Some(len))
+        let (body, len) = strip_header(src)?;
+        (body, Some(len))
     } else {
         (src, (buflen >= 0).then_some(buflen as usize))
     };
@@ -42,8 +51,170 @@ fn decompress(
         .map(|result| PyBytes::new(py, &result))
 }
 
+/// LZO decompress `src` directly into `output`.
+///
+/// Unlike `decompress`, this writes straight into the caller-supplied buffer instead of
+/// allocating a new one, which is useful when the same buffer can be reused across calls.
+///
+/// Args:
+///     src: Bytes to decompress, or a file-like object exposing ``read()``.
+///     output: A writable ``bytearray`` or writable buffer to decompress into.
+///     header: Whether the metadata header is included in the input.
+///
+/// Returns:
+///     The number of bytes written to `output`.
+///
+#[pyfunction]
+#[pyo3(signature = (src, output, header=true))]
+fn decompress_into(src: &Bound<'_, PyAny>, output: &Bound<'_, PyAny>, header: bool) -> PyResult<usize> {
+    let src = read_source(src)?;
+    let body = if header {
+        strip_header(src)?.0
+    } else {
+        src
+    };
+
+    let output = writable_bytes(output)?;
+    let mut cursor = std::io::Cursor::new(body);
+    lzokay_native::decompress_into(&mut cursor, output)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+}
+
+/// LZO compress `src` directly into `output`. Does not add the python-lzo header.
+///
+/// Unlike `compress`, this writes straight into the caller-supplied buffer instead of
+/// allocating a new one.
+///
+/// Args:
+///     src: Bytes to compress.
+///     output: A writable ``bytearray`` or writable buffer to compress into.
+///     level: LZO1X compression level, between ``1`` and ``9``.
+///
+/// Returns:
+///     The number of bytes written to `output`.
+///
+#[pyfunction]
+#[pyo3(signature = (src, output, level=1))]
+fn compress_into(src: Vec<u8>, output: &Bound<'_, PyAny>, level: i32) -> PyResult<usize> {
+    if !(1..=9).contains(&level) {
+        return Err(PyValueError::new_err("level must be between 1 and 9"));
+    }
+
+    let output = writable_bytes(output)?;
+    lzokay_native::compress_into(&src, output, level)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+}
+
+/// Build a python-lzo compatible header for already-compressed `body`.
+///
+/// https://github.com/jd-boyd/python-lzo/blob/80ca60416c6657d373c5308a1eb511903a3ff9b1/lzomodule.c#L238-L269
+fn with_header(body: Vec<u8>, uncompressed_len: usize) -> Vec<u8> {
+    let mut result = Vec::with_capacity(5 + body.len());
+    result.push(0xf1);
+    result.extend_from_slice(&(uncompressed_len as u32).to_le_bytes());
+    result.extend_from_slice(&body);
+    result
+}
+
+/// LZO compress bytes, producing output that is byte-compatible with python-lzo.
+///
+/// Args:
+///     src: Bytes to compress.
+///     level: LZO1X compression level, between ``1`` and ``9``.
+///     header: Whether to prefix the output with the python-lzo compatible header.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The compressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, level=1, header=true, return_bytearray=false))]
+fn compress(
+    py: Python<'_>,
+    src: Vec<u8>,
+    level: i32,
+    header: bool,
+    return_bytearray: bool,
+) -> PyResult<PyObject> {
+    if !(1..=9).contains(&level) {
+        return Err(PyValueError::new_err("level must be between 1 and 9"));
+    }
+
+    let body = lzokay_native::compress(&src, level)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+    let result = if header {
+        with_header(body, src.len())
+    } else {
+        body
+    };
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
+/// Run the LZO1X optimization pass over already-compressed `src`, shrinking it without
+/// changing the data it decompresses to. Assumes no header.
+///
+/// Args:
+///     src: Already LZO-compressed bytes to optimize.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The optimized, still-compressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, return_bytearray=false))]
+fn optimize(py: Python<'_>, src: Vec<u8>, return_bytearray: bool) -> PyResult<PyObject> {
+    let result = lzokay_native::optimize(&src)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
 pub fn create_submodule(m: &Bound<'_, PyModule>) -> PyResult<()> {
     let submodule = PyModule::new(m.py(), "lzo")?;
     submodule.add_function(wrap_pyfunction!(decompress, m)?)?;
+    submodule.add_function(wrap_pyfunction!(decompress_into, m)?)?;
+    submodule.add_function(wrap_pyfunction!(compress, m)?)?;
+    submodule.add_function(wrap_pyfunction!(compress_into, m)?)?;
+    submodule.add_function(wrap_pyfunction!(optimize, m)?)?;
     m.add_submodule(&submodule)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &[u8] = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    #[test]
+    fn header_round_trips() {
+        let body = lzokay_native::compress(SRC, 1).unwrap();
+        let with_header = with_header(body, SRC.len());
+
+        assert_eq!(with_header[0], 0xf1);
+
+        let (stripped, len) = strip_header(with_header).unwrap();
+        assert_eq!(len, SRC.len());
+
+        let mut cursor = std::io::Cursor::new(stripped);
+        let decompressed = lzokay_native::decompress(&mut cursor, Some(len)).unwrap();
+        assert_eq!(decompressed, SRC);
+    }
+
+    #[test]
+    fn strip_header_rejects_invalid_marker() {
+        let mut bogus = vec![0x00; 8];
+        bogus.extend_from_slice(b"payload");
+        assert!(strip_header(bogus).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::io::{self, Read};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes};
+
+use crate::source::open_reader;
+
+fn compress_reader(mut src: impl Read, level: i32, dict: Option<&[u8]>) -> io::Result<Vec<u8>> {
+    let mut encoder = match dict {
+        Some(dict) => zstd::stream::write::Encoder::with_dictionary(Vec::new(), level, dict)?,
+        None => zstd::stream::write::Encoder::new(Vec::new(), level)?,
+    };
+    io::copy(&mut src, &mut encoder)?;
+    encoder.finish()
+}
+
+fn decompress_reader(src: impl Read, dict: Option<&[u8]>) -> io::Result<Vec<u8>> {
+    let mut decoder = match dict {
+        Some(dict) => zstd::stream::read::Decoder::with_dictionary(src, dict)?,
+        None => zstd::stream::read::Decoder::new(src)?,
+    };
+    let mut result = Vec::new();
+    decoder.read_to_end(&mut result)?;
+    Ok(result)
+}
+
+/// Zstandard compress `src`.
+///
+/// Args:
+///     src: Bytes to compress, or a file-like object exposing ``read()``.
+///     level: Compression level. Higher compresses better but is slower.
+///     dict: Optional pre-shared dictionary, useful when compressing many small, similar inputs.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The compressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, level=3, dict=None, return_bytearray=false))]
+fn compress(
+    py: Python<'_>,
+    src: &Bound<'_, PyAny>,
+    level: i32,
+    dict: Option<Vec<u8>>,
+    return_bytearray: bool,
+) -> PyResult<PyObject> {
+    let reader = open_reader(src)?;
+    let result = compress_reader(reader, level, dict.as_deref())
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
+/// Zstandard decompress `src`.
+///
+/// This streams through a `zstd` decoder rather than requiring the decompressed size up
+/// front, so `src` may be a file-like object of arbitrary, unknown decompressed length.
+///
+/// Args:
+///     src: Bytes to decompress, or a file-like object exposing ``read()``.
+///     dict: Optional pre-shared dictionary, matching the one used to compress `src`.
+///     return_bytearray: Whether to return ``bytearray`` or ``bytes``.
+///
+/// Returns:
+///     The decompressed data.
+///
+#[pyfunction]
+#[pyo3(signature = (src, dict=None, return_bytearray=false))]
+fn decompress(
+    py: Python<'_>,
+    src: &Bound<'_, PyAny>,
+    dict: Option<Vec<u8>>,
+    return_bytearray: bool,
+) -> PyResult<PyObject> {
+    let reader = open_reader(src)?;
+    let result = decompress_reader(reader, dict.as_deref())
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+
+    let pyresult = PyBytes::new(py, &result);
+    if return_bytearray {
+        Ok(PyByteArray::from(&pyresult)?.into())
+    } else {
+        Ok(pyresult.into())
+    }
+}
+
+const DELTA_HEADER_LEN: usize = 8;
+
+fn diff_bytes(base: &[u8], target: &[u8]) -> io::Result<Vec<u8>> {
+    let mut compressor = zstd::bulk::Compressor::with_dictionary(0, base)?;
+    let compressed = compressor.compress(target)?;
+
+    let mut result = Vec::with_capacity(DELTA_HEADER_LEN + compressed.len());
+    result.extend_from_slice(&(target.len() as u64).to_le_bytes());
+    result.extend_from_slice(&compressed);
+    Ok(result)
+}
+
+fn apply_bytes(base: &[u8], delta: &[u8]) -> PyResult<Vec<u8>> {
+    if delta.len() < DELTA_HEADER_LEN {
+        return Err(PyValueError::new_err("delta is missing its size header"));
+    }
+    let (header, compressed) = delta.split_at(DELTA_HEADER_LEN);
+    let target_len = u64::from_le_bytes(header.try_into().unwrap()) as usize;
+
+    let mut decompressor = zstd::bulk::Decompressor::with_dictionary(base)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+    decompressor
+        .decompress(compressed, target_len)
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+}
+
+/// Zstd delta-compress `target` against `base`, producing a compact patch.
+///
+/// `base` is used as the dictionary/reference window rather than being embedded in the
+/// output, so `apply` needs the exact same `base` bytes to reconstruct `target`. The patch
+/// is prefixed with an 8-byte little-endian length of `target`, since `apply` needs to know
+/// the decompressed size up front and the compressed stream alone does not reliably expose it.
+///
+/// Args:
+///     base: Reference data that `target` is expected to be similar to.
+///     target: The data to compress.
+///
+/// Returns:
+///     A patch that `apply` can combine with `base` to reconstruct `target`.
+///
+#[pyfunction]
+fn diff(py: Python<'_>, base: Vec<u8>, target: Vec<u8>) -> PyResult<Bound<'_, PyBytes>> {
+    let result =
+        diff_bytes(&base, &target).map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+    Ok(PyBytes::new(py, &result))
+}
+
+/// Reconstruct the original data from `base` and a `delta` produced by `diff`.
+///
+/// Args:
+///     base: The same reference data that was passed to `diff`.
+///     delta: The patch produced by `diff`.
+///
+/// Returns:
+///     The reconstructed data.
+///
+#[pyfunction]
+fn apply(py: Python<'_>, base: Vec<u8>, delta: Vec<u8>) -> PyResult<Bound<'_, PyBytes>> {
+    let result = apply_bytes(&base, &delta)?;
+    Ok(PyBytes::new(py, &result))
+}
+
+pub fn create_submodule(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let submodule = PyModule::new(m.py(), "zstd")?;
+    submodule.add_function(wrap_pyfunction!(compress, m)?)?;
+    submodule.add_function(wrap_pyfunction!(decompress, m)?)?;
+    submodule.add_function(wrap_pyfunction!(diff, m)?)?;
+    submodule.add_function(wrap_pyfunction!(apply, m)?)?;
+    m.add_submodule(&submodule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_apply_round_trips() {
+        let base = b"the quick brown fox jumps over the lazy dog".to_vec();
+        let target = b"the quick brown fox jumps over the lazy cat".to_vec();
+
+        let delta = diff_bytes(&base, &target).unwrap();
+        assert_eq!(
+            u64::from_le_bytes(delta[..DELTA_HEADER_LEN].try_into().unwrap()) as usize,
+            target.len()
+        );
+
+        let result = apply_bytes(&base, &delta).unwrap();
+        assert_eq!(result, target);
+    }
+
+    #[test]
+    fn apply_rejects_truncated_delta() {
+        assert!(apply_bytes(b"base", &[0u8; DELTA_HEADER_LEN - 1]).is_err());
+    }
+}
@@ -1,9 +1,14 @@
 use pyo3::prelude::*;
 
+mod buffer;
 mod compression;
 mod hash;
+mod source;
 
-#[pymodule(gil_used = false)]
+// `gil_used = false` (free-threaded/no-GIL support) is intentionally not set: the `*_into`
+// functions in `compression::lz4::block` and `compression::lzo` hand out a mutable slice into
+// a caller-supplied Python buffer, which is only safe while the GIL serializes access to it.
+#[pymodule]
 fn _native(m: &Bound<'_, PyModule>) -> PyResult<()> {
     compression::create_submodule(m)?;
     hash::create_submodule(m)?;
@@ -0,0 +1,77 @@
+use std::io;
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Read `src` into an owned byte buffer.
+///
+/// Accepts either a `bytes`-like object, or any object exposing a file-like `read()` method
+/// (e.g. an open file or `io.BytesIO`), which is pulled incrementally in `CHUNK_SIZE` chunks
+/// rather than requiring the caller to materialize the whole input up front.
+pub fn read_source(src: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(bytes) = src.downcast::<PyBytes>() {
+        return Ok(bytes.as_bytes().to_vec());
+    }
+    if let Ok(bytearray) = src.downcast::<PyByteArray>() {
+        return Ok(bytearray.as_bytes().to_vec());
+    }
+
+    let mut buf = Vec::new();
+    loop {
+        let chunk = src.call_method1("read", (CHUNK_SIZE,))?;
+        let chunk = chunk
+            .downcast::<PyBytes>()
+            .map_err(|_| PyTypeError::new_err("read() must return bytes"))?;
+        if chunk.as_bytes().is_empty() {
+            break;
+        }
+        buf.extend_from_slice(chunk.as_bytes());
+    }
+    Ok(buf)
+}
+
+/// Adapts a Python object exposing a file-like `read()` method to `std::io::Read`, pulling
+/// chunks on demand instead of materializing the whole input up front.
+struct PyReader<'py> {
+    obj: Bound<'py, PyAny>,
+}
+
+impl<'py> io::Read for PyReader<'py> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let chunk = self
+            .obj
+            .call_method1("read", (buf.len(),))
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        let chunk = chunk
+            .downcast::<PyBytes>()
+            .map_err(|_| io::Error::other("read() must return bytes"))?;
+        let data = chunk.as_bytes();
+        if data.len() > buf.len() {
+            return Err(io::Error::other(format!(
+                "read() returned {} bytes, more than the requested {}",
+                data.len(),
+                buf.len()
+            )));
+        }
+        buf[..data.len()].copy_from_slice(data);
+        Ok(data.len())
+    }
+}
+
+/// Open `src` for genuinely incremental, zero-materialization reading.
+///
+/// Accepts either a `bytes`-like object, wrapped in a `Cursor`, or any object exposing a
+/// file-like `read()` method, which is read on demand as the returned `Read` is consumed.
+pub fn open_reader<'py>(src: &Bound<'py, PyAny>) -> PyResult<Box<dyn io::Read + 'py>> {
+    if let Ok(bytes) = src.downcast::<PyBytes>() {
+        return Ok(Box::new(io::Cursor::new(bytes.as_bytes().to_vec())));
+    }
+    if let Ok(bytearray) = src.downcast::<PyByteArray>() {
+        return Ok(Box::new(io::Cursor::new(bytearray.as_bytes().to_vec())));
+    }
+
+    Ok(Box::new(PyReader { obj: src.clone() }))
+}